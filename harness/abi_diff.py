@@ -0,0 +1,252 @@
+from __future__ import annotations
+
+import re
+from dataclasses import dataclass
+from pathlib import Path
+
+VERSION_PATTERN = re.compile(r"^pub const KOZO_ABI_VERSION: u32 = (\d+);$", re.MULTILINE)
+CONSTANT_LINE_PATTERN = re.compile(r"^pub const .*$", re.MULTILINE)
+CONSTANT_PATTERN = re.compile(r"^pub const ([A-Z0-9_]+): ([A-Za-z0-9_]+) = (\d+);$")
+TYPE_ALIAS_LINE_PATTERN = re.compile(r"^pub type .*$", re.MULTILINE)
+TYPE_ALIAS_PATTERN = re.compile(r"^pub type ([A-Z0-9_]+) = ([a-z0-9_]+);$")
+STRUCT_LINE_PATTERN = re.compile(r"^pub struct .*$", re.MULTILINE)
+STRUCT_PATTERN = re.compile(
+    r"^(?:#\[repr\((?P<repr>[^)]*)\)\]\n)?(?P<declaration>pub struct ([A-Za-z0-9_]+) \{)(?P<body>.*?)^\}",
+    re.MULTILINE | re.DOTALL,
+)
+FIELD_PATTERN = re.compile(r"pub ([a-z_][a-z0-9_]*): ([A-Za-z0-9_]+),")
+
+VERSION_CONSTANT = "KOZO_ABI_VERSION"
+
+COMPATIBILITY_IDENTICAL = "identical"
+COMPATIBILITY_ADDITIVE = "additive"
+COMPATIBILITY_BREAKING = "breaking"
+
+
+@dataclass(frozen=True)
+class StructLayout:
+    repr: str
+    fields: tuple[tuple[str, str], ...]
+
+
+@dataclass(frozen=True)
+class AbiSnapshot:
+    version: int
+    type_aliases: dict[str, str]
+    constants: dict[str, dict[str, int]]
+    layouts: dict[str, StructLayout]
+
+
+@dataclass(frozen=True)
+class ConstantDiff:
+    added: tuple[tuple[str, int], ...]
+    removed: tuple[tuple[str, int], ...]
+    renumbered: tuple[tuple[str, int, int], ...]
+
+
+@dataclass(frozen=True)
+class TypeAliasDiff:
+    added: tuple[tuple[str, str], ...]
+    removed: tuple[tuple[str, str], ...]
+    changed: tuple[tuple[str, str, str], ...]
+
+
+@dataclass(frozen=True)
+class LayoutDiff:
+    added: tuple[str, ...]
+    removed: tuple[str, ...]
+    changed: tuple[str, ...]
+
+
+@dataclass(frozen=True)
+class AbiDiff:
+    version_before: int
+    version_after: int
+    type_aliases: TypeAliasDiff
+    constants: dict[str, ConstantDiff]
+    layouts: LayoutDiff
+
+
+def load_binding_snapshot(path: Path) -> AbiSnapshot:
+    return parse_rust_binding(path.read_text())
+
+
+def parse_rust_binding(text: str) -> AbiSnapshot:
+    return AbiSnapshot(
+        version=_parse_version(text),
+        type_aliases=_parse_type_aliases(text),
+        constants=_parse_constants(text),
+        layouts=_parse_layouts(text),
+    )
+
+
+def diff_snapshots(before: AbiSnapshot, after: AbiSnapshot) -> AbiDiff:
+    return AbiDiff(
+        version_before=before.version,
+        version_after=after.version,
+        type_aliases=_diff_type_aliases(before.type_aliases, after.type_aliases),
+        constants={
+            type_name: _diff_constants(before.constants.get(type_name, {}), after.constants.get(type_name, {}))
+            for type_name in sorted(before.constants.keys() | after.constants.keys())
+        },
+        layouts=_diff_layouts(before.layouts, after.layouts),
+    )
+
+
+def compatibility(diff: AbiDiff) -> str:
+    if _has_breaking_change(diff):
+        return COMPATIBILITY_BREAKING
+    if _has_additive_change(diff):
+        return COMPATIBILITY_ADDITIVE
+    return COMPATIBILITY_IDENTICAL
+
+
+def requires_version_bump(diff: AbiDiff) -> bool:
+    return _has_breaking_change(diff) and diff.version_after <= diff.version_before
+
+
+def diff_record(diff: AbiDiff) -> dict[str, object]:
+    return {
+        "abi_version": {"before": diff.version_before, "after": diff.version_after},
+        "compatibility": compatibility(diff),
+        "version_bump_required": requires_version_bump(diff),
+        "type_aliases": {
+            "added": [{"name": name, "type": type_name} for name, type_name in diff.type_aliases.added],
+            "removed": [{"name": name, "type": type_name} for name, type_name in diff.type_aliases.removed],
+            "changed": [
+                {"name": name, "before": before, "after": after}
+                for name, before, after in diff.type_aliases.changed
+            ],
+        },
+        "constants": {
+            type_name: _constant_diff_record(constant_diff) for type_name, constant_diff in diff.constants.items()
+        },
+        "layouts": {
+            "added": list(diff.layouts.added),
+            "removed": list(diff.layouts.removed),
+            "changed": list(diff.layouts.changed),
+        },
+    }
+
+
+def _parse_version(text: str) -> int:
+    match = VERSION_PATTERN.search(text)
+    if match is None:
+        raise ValueError("KOZO_ABI_VERSION constant is missing from Rust binding")
+    return int(match.group(1))
+
+
+def _parse_constants(text: str) -> dict[str, dict[str, int]]:
+    constants: dict[str, dict[str, int]] = {}
+    for line in CONSTANT_LINE_PATTERN.findall(text):
+        match = CONSTANT_PATTERN.match(line)
+        if match is None:
+            raise ValueError(f"unrecognized constant in Rust binding: {line}")
+        name, type_name, value = match.groups()
+        if name == VERSION_CONSTANT:
+            continue
+        constants.setdefault(type_name, {})[name] = int(value)
+    return constants
+
+
+def _parse_type_aliases(text: str) -> dict[str, str]:
+    type_aliases: dict[str, str] = {}
+    for line in TYPE_ALIAS_LINE_PATTERN.findall(text):
+        match = TYPE_ALIAS_PATTERN.match(line)
+        if match is None:
+            raise ValueError(f"unrecognized type alias in Rust binding: {line}")
+        name, type_name = match.groups()
+        type_aliases[name] = type_name
+    return type_aliases
+
+
+def _parse_layouts(text: str) -> dict[str, StructLayout]:
+    layouts: dict[str, StructLayout] = {}
+    consumed: set[int] = set()
+    for match in STRUCT_PATTERN.finditer(text):
+        consumed.add(match.start("declaration"))
+        layouts[match.group(3)] = StructLayout(
+            repr=match.group("repr") or "",
+            fields=_parse_fields(match.group("body")),
+        )
+    for match in STRUCT_LINE_PATTERN.finditer(text):
+        if match.start() not in consumed:
+            raise ValueError(f"unrecognized struct in Rust binding: {match.group(0)}")
+    return layouts
+
+
+def _parse_fields(body: str) -> tuple[tuple[str, str], ...]:
+    fields = []
+    for line in body.splitlines():
+        if not line.strip():
+            continue
+        match = FIELD_PATTERN.fullmatch(line.strip())
+        if match is None:
+            raise ValueError(f"unrecognized struct field in Rust binding: {line.strip()}")
+        fields.append((match.group(1), match.group(2)))
+    return tuple(fields)
+
+
+def _diff_constants(before: dict[str, int], after: dict[str, int]) -> ConstantDiff:
+    return ConstantDiff(
+        added=tuple(sorted((name, after[name]) for name in after.keys() - before.keys())),
+        removed=tuple(sorted((name, before[name]) for name in before.keys() - after.keys())),
+        renumbered=tuple(
+            sorted(
+                (name, before[name], after[name])
+                for name in before.keys() & after.keys()
+                if before[name] != after[name]
+            )
+        ),
+    )
+
+
+def _diff_type_aliases(before: dict[str, str], after: dict[str, str]) -> TypeAliasDiff:
+    return TypeAliasDiff(
+        added=tuple(sorted((name, after[name]) for name in after.keys() - before.keys())),
+        removed=tuple(sorted((name, before[name]) for name in before.keys() - after.keys())),
+        changed=tuple(
+            sorted(
+                (name, before[name], after[name])
+                for name in before.keys() & after.keys()
+                if before[name] != after[name]
+            )
+        ),
+    )
+
+
+def _diff_layouts(before: dict[str, StructLayout], after: dict[str, StructLayout]) -> LayoutDiff:
+    return LayoutDiff(
+        added=tuple(sorted(after.keys() - before.keys())),
+        removed=tuple(sorted(before.keys() - after.keys())),
+        changed=tuple(sorted(name for name in before.keys() & after.keys() if before[name] != after[name])),
+    )
+
+
+def _has_breaking_change(diff: AbiDiff) -> bool:
+    return bool(
+        diff.type_aliases.removed
+        or diff.type_aliases.changed
+        or any(constant_diff.removed or constant_diff.renumbered for constant_diff in diff.constants.values())
+        or diff.layouts.removed
+        or diff.layouts.changed
+    )
+
+
+def _has_additive_change(diff: AbiDiff) -> bool:
+    return bool(
+        diff.type_aliases.added
+        or any(constant_diff.added for constant_diff in diff.constants.values())
+        or diff.layouts.added
+    )
+
+
+def _constant_diff_record(diff: ConstantDiff) -> dict[str, object]:
+    return {
+        "added": [{"name": name, "value": value} for name, value in diff.added],
+        "removed": [{"name": name, "value": value} for name, value in diff.removed],
+        "renumbered": [
+            {"name": name, "before": before, "after": after}
+            for name, before, after in diff.renumbered
+        ],
+    }
@@ -0,0 +1,45 @@
+#!/usr/bin/env python3
+from __future__ import annotations
+
+import argparse
+import json
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from harness import abi_diff  # noqa: E402
+
+EXIT_OK = 0
+EXIT_VERSION_BUMP_REQUIRED = 1
+EXIT_INPUT_ERROR = 2
+
+
+def _build_argument_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser(
+        description="Compare two generated KOZO Rust ABI bindings and report compatibility"
+    )
+    parser.add_argument("before", type=Path, help="Generated kozo_abi.rs from the deployed build")
+    parser.add_argument("after", type=Path, help="Generated kozo_abi.rs from the candidate build")
+    return parser
+
+
+def main(argv: list[str] | None = None) -> int:
+    args = _build_argument_parser().parse_args(argv)
+    try:
+        diff = abi_diff.diff_snapshots(
+            abi_diff.load_binding_snapshot(args.before),
+            abi_diff.load_binding_snapshot(args.after),
+        )
+    except (OSError, ValueError) as error:
+        print(f"abi_diff: {error}", file=sys.stderr)
+        return EXIT_INPUT_ERROR
+    print(json.dumps(abi_diff.diff_record(diff), indent=2))
+    if abi_diff.requires_version_bump(diff):
+        return EXIT_VERSION_BUMP_REQUIRED
+    return EXIT_OK
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
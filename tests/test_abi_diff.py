@@ -0,0 +1,300 @@
+from __future__ import annotations
+
+import contextlib
+import importlib.util
+import io
+import json
+import tempfile
+import unittest
+from pathlib import Path
+
+from harness import abi_diff
+
+ROOT = Path(__file__).resolve().parents[1]
+RUST_BINDING_PATH = ROOT / "bindings" / "rust" / "kozo_abi.rs"
+SCRIPT_PATH = ROOT / "scripts" / "abi_diff.py"
+
+
+def load_script_module():
+    spec = importlib.util.spec_from_file_location("abi_diff_script", SCRIPT_PATH)
+    module = importlib.util.module_from_spec(spec)
+    spec.loader.exec_module(module)
+    return module
+
+
+class AbiDiffTests(unittest.TestCase):
+    def test_parses_checked_in_rust_binding(self):
+        snapshot = abi_diff.load_binding_snapshot(RUST_BINDING_PATH)
+
+        self.assertEqual(snapshot.version, 1)
+        self.assertEqual(snapshot.type_aliases, {"K_HANDLE": "u64", "K_STATUS": "u32", "K_SYSCALL_ID": "u32"})
+        self.assertEqual(
+            snapshot.constants,
+            {
+                "K_STATUS": {"K_OK": 0, "K_INVALID": 1, "K_DENIED": 2},
+                "K_SYSCALL_ID": {"K_SYSCALL_NOP": 0, "K_SYSCALL_DEBUG_HEARTBEAT": 1, "K_SYSCALL_STATUS": 2},
+            },
+        )
+        self.assertEqual(
+            snapshot.layouts["HeartbeatPayload"],
+            abi_diff.StructLayout(
+                repr="C",
+                fields=(("sequence", "u64"), ("timestamp", "u64"), ("status_bits", "u32")),
+            ),
+        )
+
+    def test_identical_bindings_report_no_changes(self):
+        snapshot = self.checked_in_snapshot()
+
+        diff = abi_diff.diff_snapshots(snapshot, snapshot)
+
+        self.assertEqual(abi_diff.compatibility(diff), abi_diff.COMPATIBILITY_IDENTICAL)
+        self.assertFalse(abi_diff.requires_version_bump(diff))
+
+    def test_added_syscall_is_additive(self):
+        after = self.mutated_snapshot(
+            lambda text: text.replace(
+                "pub const K_SYSCALL_STATUS: K_SYSCALL_ID = 2;\n",
+                "pub const K_SYSCALL_STATUS: K_SYSCALL_ID = 2;\npub const K_SYSCALL_YIELD: K_SYSCALL_ID = 3;\n",
+            )
+        )
+
+        diff = abi_diff.diff_snapshots(self.checked_in_snapshot(), after)
+
+        self.assertEqual(diff.constants["K_SYSCALL_ID"].added, (("K_SYSCALL_YIELD", 3),))
+        self.assertEqual(abi_diff.compatibility(diff), abi_diff.COMPATIBILITY_ADDITIVE)
+        self.assertFalse(abi_diff.requires_version_bump(diff))
+
+    def test_removed_status_is_breaking(self):
+        after = self.mutated_snapshot(lambda text: text.replace("pub const K_DENIED: K_STATUS = 2;\n", ""))
+
+        diff = abi_diff.diff_snapshots(self.checked_in_snapshot(), after)
+
+        self.assertEqual(diff.constants["K_STATUS"].removed, (("K_DENIED", 2),))
+        self.assertEqual(abi_diff.compatibility(diff), abi_diff.COMPATIBILITY_BREAKING)
+        self.assertTrue(abi_diff.requires_version_bump(diff))
+
+    def test_renumbered_syscall_is_breaking(self):
+        after = self.mutated_snapshot(
+            lambda text: text.replace(
+                "pub const K_SYSCALL_STATUS: K_SYSCALL_ID = 2;",
+                "pub const K_SYSCALL_STATUS: K_SYSCALL_ID = 7;",
+            )
+        )
+
+        diff = abi_diff.diff_snapshots(self.checked_in_snapshot(), after)
+
+        self.assertEqual(diff.constants["K_SYSCALL_ID"].renumbered, (("K_SYSCALL_STATUS", 2, 7),))
+        self.assertEqual(abi_diff.compatibility(diff), abi_diff.COMPATIBILITY_BREAKING)
+
+    def test_constants_of_new_type_are_diffed(self):
+        after = self.mutated_snapshot(
+            lambda text: text.replace(
+                "#[repr(C)]\n",
+                "pub type K_RIGHTS = u32;\npub const K_RIGHT_READ: K_RIGHTS = 1;\n\n#[repr(C)]\n",
+            )
+        )
+
+        diff = abi_diff.diff_snapshots(self.checked_in_snapshot(), after)
+
+        self.assertEqual(diff.constants["K_RIGHTS"].added, (("K_RIGHT_READ", 1),))
+        self.assertEqual(abi_diff.compatibility(diff), abi_diff.COMPATIBILITY_ADDITIVE)
+
+    def test_removed_constant_of_other_type_is_breaking(self):
+        before = self.mutated_snapshot(
+            lambda text: text.replace("#[repr(C)]\n", "pub const K_RIGHT_READ: K_RIGHTS = 1;\n\n#[repr(C)]\n")
+        )
+
+        diff = abi_diff.diff_snapshots(before, self.checked_in_snapshot())
+
+        self.assertEqual(diff.constants["K_RIGHTS"].removed, (("K_RIGHT_READ", 1),))
+        self.assertEqual(abi_diff.compatibility(diff), abi_diff.COMPATIBILITY_BREAKING)
+
+    def test_changed_layout_is_breaking(self):
+        after = self.mutated_snapshot(lambda text: text.replace("pub status_bits: u32,", "pub status_bits: u64,"))
+
+        diff = abi_diff.diff_snapshots(self.checked_in_snapshot(), after)
+
+        self.assertEqual(diff.layouts.changed, ("HeartbeatPayload",))
+        self.assertEqual(abi_diff.compatibility(diff), abi_diff.COMPATIBILITY_BREAKING)
+
+    def test_changed_type_alias_width_is_breaking(self):
+        after = self.mutated_snapshot(
+            lambda text: text.replace("pub type K_STATUS = u32;", "pub type K_STATUS = u64;").replace(
+                "pub type K_HANDLE = u64;",
+                "pub type K_HANDLE = u32;",
+            )
+        )
+
+        diff = abi_diff.diff_snapshots(self.checked_in_snapshot(), after)
+
+        self.assertEqual(diff.type_aliases.changed, (("K_HANDLE", "u64", "u32"), ("K_STATUS", "u32", "u64")))
+        self.assertEqual(abi_diff.compatibility(diff), abi_diff.COMPATIBILITY_BREAKING)
+        self.assertTrue(abi_diff.requires_version_bump(diff))
+
+    def test_removed_struct_repr_is_breaking(self):
+        after = self.mutated_snapshot(lambda text: text.replace("#[repr(C)]\n", ""))
+
+        diff = abi_diff.diff_snapshots(self.checked_in_snapshot(), after)
+
+        self.assertEqual(diff.layouts.changed, ("HeartbeatPayload",))
+        self.assertEqual(abi_diff.compatibility(diff), abi_diff.COMPATIBILITY_BREAKING)
+        self.assertTrue(abi_diff.requires_version_bump(diff))
+
+    def test_breaking_change_with_version_bump_does_not_require_another_bump(self):
+        after = self.mutated_snapshot(
+            lambda text: text.replace("pub const K_DENIED: K_STATUS = 2;\n", "").replace(
+                "pub const KOZO_ABI_VERSION: u32 = 1;",
+                "pub const KOZO_ABI_VERSION: u32 = 2;",
+            )
+        )
+
+        diff = abi_diff.diff_snapshots(self.checked_in_snapshot(), after)
+
+        self.assertEqual(abi_diff.compatibility(diff), abi_diff.COMPATIBILITY_BREAKING)
+        self.assertFalse(abi_diff.requires_version_bump(diff))
+
+    def test_diff_record_is_machine_readable(self):
+        after = self.mutated_snapshot(
+            lambda text: text.replace(
+                "pub const K_SYSCALL_NOP: K_SYSCALL_ID = 0;",
+                "pub const K_SYSCALL_NOP: K_SYSCALL_ID = 9;",
+            )
+        )
+
+        record = abi_diff.diff_record(abi_diff.diff_snapshots(self.checked_in_snapshot(), after))
+
+        self.assertEqual(record["abi_version"], {"before": 1, "after": 1})
+        self.assertEqual(record["compatibility"], "breaking")
+        self.assertTrue(record["version_bump_required"])
+        self.assertEqual(
+            record["constants"]["K_SYSCALL_ID"]["renumbered"],
+            [{"name": "K_SYSCALL_NOP", "before": 0, "after": 9}],
+        )
+
+    def test_rejects_binding_without_version(self):
+        text = RUST_BINDING_PATH.read_text().replace("pub const KOZO_ABI_VERSION: u32 = 1;\n", "")
+
+        with self.assertRaises(ValueError):
+            abi_diff.parse_rust_binding(text)
+
+    def test_rejects_unrecognized_constant(self):
+        text = RUST_BINDING_PATH.read_text().replace(
+            "pub const K_OK: K_STATUS = 0;",
+            "pub const K_OK: K_STATUS = 0x0;",
+        )
+
+        with self.assertRaises(ValueError):
+            abi_diff.parse_rust_binding(text)
+
+    def test_rejects_unrecognized_struct_field(self):
+        text = RUST_BINDING_PATH.read_text().replace(
+            "    pub status_bits: u32,\n",
+            "    pub status_bits: u32,\n    pub reserved: [u8; 16],\n",
+        )
+
+        with self.assertRaises(ValueError):
+            abi_diff.parse_rust_binding(text)
+
+    def test_rejects_unrecognized_type_alias(self):
+        text = RUST_BINDING_PATH.read_text().replace(
+            "pub type K_HANDLE = u64;",
+            "pub type K_HANDLE = core::num::NonZeroU64;",
+        )
+
+        with self.assertRaises(ValueError):
+            abi_diff.parse_rust_binding(text)
+
+    def test_rejects_unrecognized_struct(self):
+        text = RUST_BINDING_PATH.read_text() + "\npub struct EmptyPayload;\n"
+
+        with self.assertRaises(ValueError):
+            abi_diff.parse_rust_binding(text)
+
+    def checked_in_snapshot(self) -> abi_diff.AbiSnapshot:
+        return abi_diff.load_binding_snapshot(RUST_BINDING_PATH)
+
+    def mutated_snapshot(self, mutate) -> abi_diff.AbiSnapshot:
+        return abi_diff.parse_rust_binding(mutate(RUST_BINDING_PATH.read_text()))
+
+
+class AbiDiffScriptTests(unittest.TestCase):
+    def test_breaking_change_without_version_bump_exits_nonzero(self):
+        exit_code, record = self.run_script(
+            lambda text: text.replace("pub const K_DENIED: K_STATUS = 2;\n", "")
+        )
+
+        self.assertEqual(exit_code, 1)
+        self.assertEqual(record["compatibility"], "breaking")
+        self.assertTrue(record["version_bump_required"])
+
+    def test_breaking_change_with_version_bump_exits_zero(self):
+        exit_code, record = self.run_script(
+            lambda text: text.replace("pub const K_DENIED: K_STATUS = 2;\n", "").replace(
+                "pub const KOZO_ABI_VERSION: u32 = 1;",
+                "pub const KOZO_ABI_VERSION: u32 = 2;",
+            )
+        )
+
+        self.assertEqual(exit_code, 0)
+        self.assertEqual(record["compatibility"], "breaking")
+        self.assertFalse(record["version_bump_required"])
+
+    def test_additive_change_exits_zero(self):
+        exit_code, record = self.run_script(
+            lambda text: text.replace(
+                "pub const K_SYSCALL_STATUS: K_SYSCALL_ID = 2;\n",
+                "pub const K_SYSCALL_STATUS: K_SYSCALL_ID = 2;\npub const K_SYSCALL_YIELD: K_SYSCALL_ID = 3;\n",
+            )
+        )
+
+        self.assertEqual(exit_code, 0)
+        self.assertEqual(record["compatibility"], "additive")
+
+    def test_identical_bindings_exit_zero(self):
+        exit_code, record = self.run_script(lambda text: text)
+
+        self.assertEqual(exit_code, 0)
+        self.assertEqual(record["compatibility"], "identical")
+        self.assertFalse(record["version_bump_required"])
+
+    def test_unparseable_binding_exits_with_input_error(self):
+        exit_code, output, errors = self.run_script_raw(
+            lambda text: text.replace("pub const KOZO_ABI_VERSION: u32 = 1;\n", "")
+        )
+
+        self.assertEqual(exit_code, 2)
+        self.assertEqual(output, "")
+        self.assertIn("KOZO_ABI_VERSION", errors)
+        self.assertEqual(len(errors.splitlines()), 1)
+
+    def test_missing_binding_exits_with_input_error(self):
+        script = load_script_module()
+        errors = io.StringIO()
+        with tempfile.TemporaryDirectory() as temp, contextlib.redirect_stderr(errors):
+            exit_code = script.main([str(RUST_BINDING_PATH), str(Path(temp) / "missing.rs")])
+
+        self.assertEqual(exit_code, 2)
+        self.assertEqual(len(errors.getvalue().splitlines()), 1)
+
+    def run_script(self, mutate) -> tuple[int, dict[str, object]]:
+        exit_code, output, _ = self.run_script_raw(mutate)
+        return exit_code, json.loads(output)
+
+    def run_script_raw(self, mutate) -> tuple[int, str, str]:
+        script = load_script_module()
+        text = RUST_BINDING_PATH.read_text()
+        with tempfile.TemporaryDirectory() as temp:
+            before = Path(temp) / "before.rs"
+            after = Path(temp) / "after.rs"
+            before.write_text(text)
+            after.write_text(mutate(text))
+            output = io.StringIO()
+            errors = io.StringIO()
+            with contextlib.redirect_stdout(output), contextlib.redirect_stderr(errors):
+                exit_code = script.main([str(before), str(after)])
+        return exit_code, output.getvalue(), errors.getvalue()
+
+
+if __name__ == "__main__":
+    unittest.main()